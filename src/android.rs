@@ -0,0 +1,79 @@
+//! Android support for `getifaddrs`/`freeifaddrs`.
+//!
+//! Android doesn't reliably expose the `getifaddrs` family through static linking across API
+//! levels, so on this platform we resolve the two symbols ourselves via `dlopen("libc.so")` the
+//! first time they're needed, and cache the result for the life of the process.
+
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::c_int;
+
+use libc::{self, ifaddrs};
+
+use error::{InterfacesError, Result};
+
+type GetifaddrsFn = unsafe extern "C" fn(*mut *mut ifaddrs) -> c_int;
+type FreeifaddrsFn = unsafe extern "C" fn(*mut ifaddrs);
+
+struct Symbols {
+    getifaddrs: Option<GetifaddrsFn>,
+    freeifaddrs: Option<FreeifaddrsFn>,
+}
+
+// The resolved symbols are plain function pointers into `libc.so`, which is as `Sync` as any
+// other `fn` item.
+unsafe impl Sync for Symbols {}
+
+lazy_static! {
+    static ref SYMBOLS: Symbols = load_symbols();
+}
+
+fn load_symbols() -> Symbols {
+    let lib_name = CString::new("libc.so").expect("static string has no interior nul");
+    let handle = unsafe { libc::dlopen(lib_name.as_ptr(), libc::RTLD_LAZY) };
+
+    if handle.is_null() {
+        return Symbols { getifaddrs: None, freeifaddrs: None };
+    }
+
+    Symbols {
+        getifaddrs: unsafe { lookup(handle, "getifaddrs") },
+        freeifaddrs: unsafe { lookup(handle, "freeifaddrs") },
+    }
+}
+
+/// Resolves `symbol` in the shared object referenced by `handle`, transmuting it to `F` (a `fn`
+/// pointer type) if found.
+unsafe fn lookup<F: Copy>(handle: *mut libc::c_void, symbol: &str) -> Option<F> {
+    let name = CString::new(symbol).expect("symbol name has no interior nul");
+    let sym = libc::dlsym(handle, name.as_ptr());
+
+    if sym.is_null() {
+        None
+    } else {
+        Some(mem::transmute_copy(&sym))
+    }
+}
+
+/// Calls the dynamically-resolved `getifaddrs`, returning an error if the symbol couldn't be
+/// resolved or the call itself failed.
+pub(crate) fn getifaddrs(ifap: *mut *mut ifaddrs) -> Result<()> {
+    let f = SYMBOLS
+        .getifaddrs
+        .ok_or(InterfacesError::Unsupported("getifaddrs is not available on this device"))?;
+
+    if unsafe { f(ifap) } != 0 {
+        return Err(InterfacesError::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Calls the dynamically-resolved `freeifaddrs`, silently doing nothing if the symbol couldn't
+/// be resolved (in which case `getifaddrs` above never handed out a list to free in the first
+/// place).
+pub(crate) fn freeifaddrs(ifap: *mut ifaddrs) {
+    if let Some(f) = SYMBOLS.freeifaddrs {
+        unsafe { f(ifap) };
+    }
+}
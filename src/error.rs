@@ -0,0 +1,61 @@
+//! Error types returned by this crate.
+
+use std::error;
+use std::fmt;
+use std::io;
+
+/// The result type used throughout this crate.
+pub type Result<T> = ::std::result::Result<T, InterfacesError>;
+
+/// The error type returned by fallible operations in this crate.
+#[derive(Debug)]
+pub enum InterfacesError {
+    /// A system call (e.g. `socket(2)` or an `ioctl(2)`) failed.  The wrapped `io::Error` carries
+    /// the OS error code (`errno`) that was set by the call.
+    SystemError(io::Error),
+
+    /// An interface name did not fit into the fixed-size `ifr_name` field of a `struct ifreq`.
+    NameTooLong,
+
+    /// A value (e.g. a hardware address) could not be parsed from its textual representation.
+    ParseError(String),
+
+    /// The requested operation has no implementation on the current platform (e.g. setting a
+    /// hardware address on macOS/BSD, where it isn't done via an `ioctl` at all).
+    Unsupported(&'static str),
+}
+
+impl InterfacesError {
+    /// Builds an `InterfacesError` from the current value of `errno`.
+    pub(crate) fn last_os_error() -> InterfacesError {
+        InterfacesError::SystemError(io::Error::last_os_error())
+    }
+}
+
+impl fmt::Display for InterfacesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InterfacesError::SystemError(ref e) => write!(f, "system error: {}", e),
+            InterfacesError::NameTooLong => write!(f, "interface name too long"),
+            InterfacesError::ParseError(ref s) => write!(f, "parse error: {}", s),
+            InterfacesError::Unsupported(ref op) => write!(f, "unsupported on this platform: {}", op),
+        }
+    }
+}
+
+impl error::Error for InterfacesError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            InterfacesError::SystemError(ref e) => Some(e),
+            InterfacesError::NameTooLong
+            | InterfacesError::ParseError(_)
+            | InterfacesError::Unsupported(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for InterfacesError {
+    fn from(e: io::Error) -> InterfacesError {
+        InterfacesError::SystemError(e)
+    }
+}
@@ -0,0 +1,44 @@
+//! Flags describing the state and capabilities of an interface, as returned by
+//! `SIOCGIFFLAGS` (or the BSD/macOS equivalent).
+
+bitflags! {
+    /// Flags describing the state and capabilities of an interface.
+    ///
+    /// These mirror the `IFF_*` constants from `<net/if.h>`; the numeric values match the ones
+    /// used on Linux, which is also where the `struct ifreq`-based accessors on `Interface` are
+    /// exercised most.
+    pub struct InterfaceFlags: u32 {
+        /// Interface is up.
+        const IFF_UP = 0x1;
+
+        /// Broadcast address is valid.
+        const IFF_BROADCAST = 0x2;
+
+        /// Turn on debugging.
+        const IFF_DEBUG = 0x4;
+
+        /// Interface is a loopback interface.
+        const IFF_LOOPBACK = 0x8;
+
+        /// Interface is a point-to-point link.
+        const IFF_POINTOPOINT = 0x10;
+
+        /// Avoid use of trailers.
+        const IFF_NOTRAILERS = 0x20;
+
+        /// Interface's resources have been allocated (i.e. it is running).
+        const IFF_RUNNING = 0x40;
+
+        /// No ARP protocol on this interface.
+        const IFF_NOARP = 0x80;
+
+        /// Interface is in promiscuous mode.
+        const IFF_PROMISC = 0x100;
+
+        /// Receive all multicast packets.
+        const IFF_ALLMULTI = 0x200;
+
+        /// Supports multicast.
+        const IFF_MULTICAST = 0x1000;
+    }
+}
@@ -0,0 +1,466 @@
+//! Default-gateway discovery, i.e. finding the next-hop router that reaches the wider internet.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use error::Result;
+use HardwareAddr;
+use Interface;
+
+/// The next-hop router for a default route.
+#[derive(Debug, Clone, Copy)]
+pub struct Gateway {
+    /// The gateway's IP address.
+    pub ip_addr: IpAddr,
+
+    /// The gateway's hardware (MAC) address, if it could be resolved from the local
+    /// ARP/neighbor cache.
+    pub mac_addr: Option<HardwareAddr>,
+}
+
+impl fmt::Display for Gateway {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mac_addr {
+            Some(ref mac) => write!(f, "{} ({})", self.ip_addr, mac),
+            None => write!(f, "{}", self.ip_addr),
+        }
+    }
+}
+
+/// Returns the system's default gateway, i.e. the next hop used to reach the wider internet,
+/// regardless of which interface it is reachable through.
+///
+/// `mac_addr` is only populated if the gateway's link-layer address is already present in the
+/// local ARP/neighbor cache; if the kernel hasn't resolved it yet, `mac_addr` comes back `None`.
+pub fn get_default_gateway() -> Result<Gateway> {
+    sys::default_gateway(None)
+}
+
+impl Interface {
+    /// Returns the default gateway reachable through this particular interface.
+    ///
+    /// See the note on `mac_addr` at `get_default_gateway()`.
+    pub fn default_gateway(&self) -> Result<Gateway> {
+        sys::default_gateway(Some(&self.name))
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use std::fs;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use error::{InterfacesError, Result};
+    use HardwareAddr;
+
+    use super::Gateway;
+
+    pub(super) fn default_gateway(iface_filter: Option<&str>) -> Result<Gateway> {
+        let (iface, ip_addr) = best_route(iface_filter)?;
+        let mac_addr = resolve_arp(&iface, ip_addr)?;
+
+        Ok(Gateway { ip_addr: IpAddr::V4(ip_addr), mac_addr })
+    }
+
+    /// Parses `/proc/net/route`, returning the interface name and gateway of the default route
+    /// (destination `0.0.0.0`) with the lowest metric, optionally restricted to `iface_filter`.
+    fn best_route(iface_filter: Option<&str>) -> Result<(String, Ipv4Addr)> {
+        let contents = fs::read_to_string("/proc/net/route")?;
+        best_route_from(&contents, iface_filter)
+    }
+
+    /// The parsing half of `best_route()`, taking `/proc/net/route`'s contents directly so it can
+    /// be exercised with a hand-built routing table.
+    fn best_route_from(contents: &str, iface_filter: Option<&str>) -> Result<(String, Ipv4Addr)> {
+        let mut best: Option<(String, Ipv4Addr, u32)> = None;
+
+        for line in contents.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 {
+                continue;
+            }
+
+            let iface = fields[0];
+            let destination = fields[1];
+            let gateway = fields[2];
+            let metric: u32 = fields[6].parse().unwrap_or(0);
+
+            if destination != "00000000" || gateway == "00000000" {
+                continue;
+            }
+
+            if let Some(filter) = iface_filter {
+                if iface != filter {
+                    continue;
+                }
+            }
+
+            if best.as_ref().is_none_or(|&(_, _, best_metric)| metric < best_metric) {
+                best = Some((iface.to_owned(), parse_hex_be_ipv4(gateway)?, metric));
+            }
+        }
+
+        best.map(|(iface, ip, _)| (iface, ip))
+            .ok_or_else(|| InterfacesError::ParseError("no default route found".to_owned()))
+    }
+
+    /// `/proc/net/route` prints each address as the hex digits of the raw `sin_addr`-style
+    /// integer in the kernel's native byte order (e.g. `0101A8C0` for `192.168.1.1` on a
+    /// little-endian host) — round-trip it the same way this crate's `sockaddr_in` parsing does,
+    /// via `u32::from_be`, rather than assuming a little-endian host.
+    fn parse_hex_be_ipv4(hex: &str) -> Result<Ipv4Addr> {
+        let value = u32::from_str_radix(hex, 16)
+            .map_err(|_| InterfacesError::ParseError(format!("invalid route address: {}", hex)))?;
+
+        Ok(Ipv4Addr::from(u32::from_be(value)))
+    }
+
+    /// Resolves `ip`'s hardware address by scanning `/proc/net/arp`.
+    fn resolve_arp(iface: &str, ip: Ipv4Addr) -> Result<Option<HardwareAddr>> {
+        let contents = fs::read_to_string("/proc/net/arp")?;
+        Ok(resolve_arp_from(&contents, iface, ip))
+    }
+
+    /// The parsing half of `resolve_arp()`, taking `/proc/net/arp`'s contents directly so it can
+    /// be exercised with a hand-built ARP cache.
+    fn resolve_arp_from(contents: &str, iface: &str, ip: Ipv4Addr) -> Option<HardwareAddr> {
+        for line in contents.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                continue;
+            }
+
+            if fields[0].parse::<Ipv4Addr>().ok() != Some(ip) || fields[5] != iface {
+                continue;
+            }
+
+            if let Ok(mac) = fields[3].parse::<HardwareAddr>() {
+                if mac != HardwareAddr::zero() {
+                    return Some(mac);
+                }
+            }
+        }
+
+        None
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_best_route_from_picks_default_route_with_lowest_metric() {
+            let contents = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                             eth1\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+                             eth0\t00000000\t0201A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+                             eth0\t0000FEA9\t00000000\t0001\t0\t0\t1000\t0000FFFF\t0\t0\t0\n";
+
+            let (iface, gateway) = best_route_from(contents, None).expect("a default route");
+            assert_eq!(iface, "eth0");
+            assert_eq!(gateway, Ipv4Addr::new(192, 168, 1, 2));
+        }
+
+        #[test]
+        fn test_best_route_from_honors_iface_filter() {
+            let contents = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                             eth1\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+                             eth0\t00000000\t0201A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n";
+
+            let (iface, gateway) = best_route_from(contents, Some("eth1")).expect("eth1's route");
+            assert_eq!(iface, "eth1");
+            assert_eq!(gateway, Ipv4Addr::new(192, 168, 1, 1));
+        }
+
+        #[test]
+        fn test_best_route_from_errors_when_no_default_route_present() {
+            let contents = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                             eth0\t0000FEA9\t00000000\t0001\t0\t0\t1000\t0000FFFF\t0\t0\t0\n";
+
+            assert!(best_route_from(contents, None).is_err());
+        }
+
+        #[test]
+        fn test_resolve_arp_from_finds_matching_entry_on_iface() {
+            let contents = "IP address       HW type     Flags       HW address            Mask     Device\n\
+                             192.168.1.1      0x1         0x2         aa:bb:cc:dd:ee:ff     *        eth0\n\
+                             192.168.1.2      0x1         0x2         00:00:00:00:00:00     *        eth0\n";
+
+            let mac = resolve_arp_from(contents, "eth0", Ipv4Addr::new(192, 168, 1, 1));
+            assert_eq!(mac, Some("aa:bb:cc:dd:ee:ff".parse().unwrap()));
+        }
+
+        #[test]
+        fn test_resolve_arp_from_ignores_unresolved_and_wrong_iface_entries() {
+            let contents = "IP address       HW type     Flags       HW address            Mask     Device\n\
+                             192.168.1.1      0x1         0x2         00:00:00:00:00:00     *        eth0\n\
+                             192.168.1.1      0x1         0x2         aa:bb:cc:dd:ee:ff     *        eth1\n";
+
+            assert_eq!(resolve_arp_from(contents, "eth0", Ipv4Addr::new(192, 168, 1, 1)), None);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod sys {
+    use std::ffi::CStr;
+    use std::mem;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::ptr;
+
+    use error::{InterfacesError, Result};
+    use HardwareAddr;
+
+    use dl::hwaddr_from_sockaddr_dl;
+
+    use super::Gateway;
+
+    /// Requests the routing table via `sysctl({CTL_NET, PF_ROUTE, 0, AF_INET, NET_RT_DUMP, 0})`
+    /// and walks the returned `rt_msghdr` records looking for the default route, restricted to
+    /// `iface_filter` (by `if_indextoname`-resolving each candidate's `rtm_index`) on the
+    /// `Interface::default_gateway` path.  The gateway's MAC, if the kernel has it cached, comes
+    /// from a second pass over the same dump looking for the `AF_LINK` neighbor entry for the
+    /// gateway's IP (mirroring how the Linux backend consults `/proc/net/arp` separately).
+    pub(super) fn default_gateway(iface_filter: Option<&str>) -> Result<Gateway> {
+        let buf = dump_routing_table()?;
+
+        let mut offset = 0;
+        while offset + mem::size_of::<libc::rt_msghdr>() <= buf.len() {
+            let rtm = unsafe { &*(buf[offset..].as_ptr() as *const libc::rt_msghdr) };
+            let msg_len = rtm.rtm_msglen as usize;
+            if msg_len == 0 {
+                break;
+            }
+
+            let in_filter = match iface_filter {
+                Some(filter) => index_to_name(rtm.rtm_index as libc::c_uint).as_deref() == Some(filter),
+                None => true,
+            };
+
+            if in_filter && rtm.rtm_addrs & libc::RTA_DST != 0 && rtm.rtm_addrs & libc::RTA_GATEWAY != 0 {
+                if let Some(ip_addr) = read_default_route(rtm, &buf[offset..offset + msg_len]) {
+                    let mac_addr = resolve_link_layer(&buf, ip_addr);
+                    return Ok(Gateway { ip_addr: IpAddr::V4(ip_addr), mac_addr });
+                }
+            }
+
+            offset += msg_len;
+        }
+
+        Err(InterfacesError::ParseError("no default route found".to_owned()))
+    }
+
+    /// Resolves `index` (an `rtm_index` from a routing-socket record) to an interface name via
+    /// `if_indextoname(3)`.
+    fn index_to_name(index: libc::c_uint) -> Option<String> {
+        let mut buf = [0 as libc::c_char; libc::IFNAMSIZ];
+        if unsafe { libc::if_indextoname(index, buf.as_mut_ptr()) }.is_null() {
+            return None;
+        }
+
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().ok().map(str::to_owned)
+    }
+
+    fn dump_routing_table() -> Result<Vec<u8>> {
+        let mibs: [libc::c_int; 6] = [
+            libc::CTL_NET,
+            libc::PF_ROUTE,
+            0,
+            libc::AF_INET,
+            libc::NET_RT_DUMP,
+            0,
+        ];
+
+        let mut needed: libc::size_t = 0;
+        if unsafe {
+            libc::sysctl(
+                mibs.as_ptr() as *mut _,
+                mibs.len() as u32,
+                ptr::null_mut(),
+                &mut needed,
+                ptr::null_mut(),
+                0,
+            )
+        } != 0
+        {
+            return Err(InterfacesError::last_os_error());
+        }
+
+        let mut buf: Vec<u8> = vec![0; needed];
+        if unsafe {
+            libc::sysctl(
+                mibs.as_ptr() as *mut _,
+                mibs.len() as u32,
+                buf.as_mut_ptr() as *mut _,
+                &mut needed,
+                ptr::null_mut(),
+                0,
+            )
+        } != 0
+        {
+            return Err(InterfacesError::last_os_error());
+        }
+        buf.truncate(needed);
+
+        Ok(buf)
+    }
+
+    /// Reads the `RTA_DST` and `RTA_GATEWAY` `sockaddr`s out of a single `rt_msghdr` record,
+    /// returning the gateway IP if the destination is `0.0.0.0`.
+    fn read_default_route(rtm: &libc::rt_msghdr, record: &[u8]) -> Option<Ipv4Addr> {
+        let mut dst_is_default = false;
+        let mut gateway_addr = None;
+
+        for_each_sockaddr(rtm, record, |i, sa, sa_bytes| {
+            if i == libc::RTAX_DST as i32 && sa.sa_family as i32 == libc::AF_INET {
+                let sin = unsafe { &*(sa_bytes.as_ptr() as *const libc::sockaddr_in) };
+                dst_is_default = sin.sin_addr.s_addr == 0;
+            } else if i == libc::RTAX_GATEWAY as i32 && sa.sa_family as i32 == libc::AF_INET {
+                let sin = unsafe { &*(sa_bytes.as_ptr() as *const libc::sockaddr_in) };
+                gateway_addr = Some(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)));
+            }
+        });
+
+        if dst_is_default {
+            gateway_addr
+        } else {
+            None
+        }
+    }
+
+    /// Scans every `rt_msghdr` record in `buf` for the host/neighbor entry whose destination is
+    /// `target` and whose gateway `sockaddr` is `AF_LINK` (i.e. an ARP-resolved entry), returning
+    /// its hardware address.
+    fn resolve_link_layer(buf: &[u8], target: Ipv4Addr) -> Option<HardwareAddr> {
+        let mut offset = 0;
+        while offset + mem::size_of::<libc::rt_msghdr>() <= buf.len() {
+            let rtm = unsafe { &*(buf[offset..].as_ptr() as *const libc::rt_msghdr) };
+            let msg_len = rtm.rtm_msglen as usize;
+            if msg_len == 0 {
+                break;
+            }
+
+            if rtm.rtm_addrs & libc::RTA_DST != 0 && rtm.rtm_addrs & libc::RTA_GATEWAY != 0 {
+                if let Some(mac) = read_link_layer_neighbor(rtm, &buf[offset..offset + msg_len], target) {
+                    return Some(mac);
+                }
+            }
+
+            offset += msg_len;
+        }
+
+        None
+    }
+
+    /// If `record`'s destination is `target` and its gateway `sockaddr` is an `AF_LINK`
+    /// `sockaddr_dl`, returns the hardware address carried in `sdl_data[sdl_nlen..sdl_nlen +
+    /// sdl_alen]` (per `<net/if_dl.h>`).
+    fn read_link_layer_neighbor(rtm: &libc::rt_msghdr, record: &[u8], target: Ipv4Addr) -> Option<HardwareAddr> {
+        let mut dst_matches = false;
+        let mut hardware_addr = None;
+
+        for_each_sockaddr(rtm, record, |i, sa, sa_bytes| {
+            if i == libc::RTAX_DST as i32 && sa.sa_family as i32 == libc::AF_INET {
+                let sin = unsafe { &*(sa_bytes.as_ptr() as *const libc::sockaddr_in) };
+                dst_matches = Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)) == target;
+            } else if i == libc::RTAX_GATEWAY as i32 && sa.sa_family as i32 == libc::AF_LINK {
+                let sdl = unsafe { &*(sa_bytes.as_ptr() as *const libc::sockaddr_dl) };
+                hardware_addr = hwaddr_from_sockaddr_dl(sdl);
+            }
+        });
+
+        if dst_matches {
+            hardware_addr
+        } else {
+            None
+        }
+    }
+
+    /// Walks the `sockaddr`s following a single `rt_msghdr`'s fixed header, calling `f(rtax,
+    /// sockaddr, sockaddr_bytes)` for each one present in `rtm_addrs`.
+    ///
+    /// The wire format only carries a `sockaddr` for indices whose bit is actually set in
+    /// `rtm_addrs` — there's no placeholder for absent ones — so an index with a clear bit must
+    /// be skipped entirely rather than read and advanced past, or every `sockaddr` after the
+    /// first gap gets misattributed to the wrong `RTAX_*` index.
+    fn for_each_sockaddr<F: FnMut(i32, &libc::sockaddr, &[u8])>(rtm: &libc::rt_msghdr, record: &[u8], mut f: F) {
+        let sockaddrs_start = mem::size_of::<libc::rt_msghdr>();
+        if sockaddrs_start > record.len() {
+            return;
+        }
+        let mut cursor = &record[sockaddrs_start..];
+
+        for i in 0..(libc::RTAX_MAX as i32) {
+            if rtm.rtm_addrs & (1 << i) == 0 {
+                continue;
+            }
+            if cursor.len() < mem::size_of::<libc::sockaddr>() {
+                break;
+            }
+
+            let sa = unsafe { &*(cursor.as_ptr() as *const libc::sockaddr) };
+            let sa_len = if sa.sa_len == 0 { mem::size_of::<libc::sockaddr>() } else { sa.sa_len as usize };
+            let aligned_len = (sa_len + mem::size_of::<libc::c_long>() - 1)
+                & !(mem::size_of::<libc::c_long>() - 1);
+
+            f(i, sa, &cursor[..sa_len.min(cursor.len())]);
+
+            if aligned_len == 0 || aligned_len > cursor.len() {
+                break;
+            }
+            cursor = &cursor[aligned_len..];
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::mem::size_of;
+
+        /// Hand-builds a single `rt_msghdr` record carrying only `RTAX_DST` (0) and `RTAX_NETMASK`
+        /// (2), with `RTAX_GATEWAY` (1) absent in between — the gap `for_each_sockaddr` must skip
+        /// over without consuming bytes that actually belong to `RTAX_NETMASK`.
+        #[test]
+        fn test_for_each_sockaddr_skips_addresses_missing_from_rtm_addrs() {
+            let sa_len = size_of::<libc::sockaddr_in>();
+            let aligned_len =
+                (sa_len + size_of::<libc::c_long>() - 1) & !(size_of::<libc::c_long>() - 1);
+
+            let mut record = vec![0u8; size_of::<libc::rt_msghdr>()];
+            let mut push_sockaddr_in = |addr: u32| {
+                let mut buf = vec![0u8; aligned_len];
+                {
+                    let sin = unsafe { &mut *(buf.as_mut_ptr() as *mut libc::sockaddr_in) };
+                    sin.sin_len = sa_len as u8;
+                    sin.sin_family = libc::AF_INET as libc::sa_family_t;
+                    sin.sin_addr.s_addr = addr;
+                }
+                record.extend_from_slice(&buf);
+            };
+
+            push_sockaddr_in(u32::from_ne_bytes([10, 0, 0, 1]));
+            push_sockaddr_in(u32::from_ne_bytes([255, 255, 255, 0]));
+
+            let rtm = libc::rt_msghdr {
+                rtm_addrs: (1 << libc::RTAX_DST) | (1 << libc::RTAX_NETMASK),
+                rtm_msglen: record.len() as u16,
+                ..unsafe { mem::zeroed() }
+            };
+
+            let mut seen = Vec::new();
+            for_each_sockaddr(&rtm, &record, |i, sa, sa_bytes| {
+                let sin = unsafe { &*(sa_bytes.as_ptr() as *const libc::sockaddr_in) };
+                assert_eq!(sa.sa_family as i32, libc::AF_INET);
+                seen.push((i, sin.sin_addr.s_addr));
+            });
+
+            assert_eq!(
+                seen,
+                vec![
+                    (libc::RTAX_DST as i32, u32::from_ne_bytes([10, 0, 0, 1])),
+                    (libc::RTAX_NETMASK as i32, u32::from_ne_bytes([255, 255, 255, 0])),
+                ]
+            );
+        }
+    }
+
+}
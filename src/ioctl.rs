@@ -0,0 +1,119 @@
+//! `ifreq`-based ioctls used to read and mutate the kernel's view of an interface.
+//!
+//! These are kept in one place since every accessor in `Interface` follows the same shape: fill
+//! in `ifr_name`, hand the request to the kernel via an `AF_INET` datagram socket, then read or
+//! write the relevant member of the `ifr_ifru` union.
+
+use std::mem;
+
+use libc::{self, c_int, c_short};
+
+use error::{InterfacesError, Result};
+
+#[repr(C)]
+pub(crate) union IfreqData {
+    pub(crate) flags: c_short,
+    pub(crate) mtu: c_int,
+    pub(crate) hwaddr: libc::sockaddr,
+    // Pads the union out to the largest `ifr_ifru` member (Linux's `struct ifmap`, 24 bytes on
+    // 64-bit targets), since the kernel copies a whole `struct ifreq` in and back out.
+    _pad: [u8; 24],
+}
+
+/// A C `struct ifreq`, laid out the way the kernel expects it.
+#[repr(C)]
+pub(crate) struct Ifreq {
+    pub(crate) name: [libc::c_char; libc::IFNAMSIZ],
+    pub(crate) data: IfreqData,
+}
+
+impl Ifreq {
+    pub(crate) fn for_name(name: &str) -> Result<Ifreq> {
+        if name.len() >= libc::IFNAMSIZ {
+            return Err(InterfacesError::NameTooLong);
+        }
+
+        let mut ifr_name = [0 as libc::c_char; libc::IFNAMSIZ];
+        for (dst, src) in ifr_name.iter_mut().zip(name.as_bytes()) {
+            *dst = *src as libc::c_char;
+        }
+
+        Ok(Ifreq {
+            name: ifr_name,
+            data: unsafe { mem::zeroed() },
+        })
+    }
+}
+
+/// Issues `ioctl(fd, request, &mut ifr)`, translating a nonzero return into an
+/// `InterfacesError` built from the current `errno`.
+pub(crate) unsafe fn ioctl(sock: c_int, request: libc::c_ulong, ifr: *mut Ifreq) -> Result<()> {
+    if libc::ioctl(sock, request as _, ifr) != 0 {
+        return Err(InterfacesError::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Fetches `name`'s current flags via `SIOCGIFFLAGS`. Portable: every target this crate supports
+/// implements this `ioctl`.
+pub(crate) fn get_flags(sock: c_int, name: &str) -> Result<c_short> {
+    let mut ifr = Ifreq::for_name(name)?;
+
+    unsafe {
+        ioctl(sock, libc::SIOCGIFFLAGS as libc::c_ulong, &mut ifr)?;
+        Ok(ifr.data.flags)
+    }
+}
+
+/// Writes `name`'s flags back via `SIOCSIFFLAGS`. Portable; see `get_flags`.
+pub(crate) fn set_flags(sock: c_int, name: &str, flags: c_short) -> Result<()> {
+    let mut ifr = Ifreq::for_name(name)?;
+    ifr.data.flags = flags;
+
+    unsafe { ioctl(sock, libc::SIOCSIFFLAGS as libc::c_ulong, &mut ifr) }
+}
+
+/// Fetches `name`'s MTU via `SIOCGIFMTU`. Portable; see `get_flags`.
+pub(crate) fn get_mtu(sock: c_int, name: &str) -> Result<c_int> {
+    let mut ifr = Ifreq::for_name(name)?;
+
+    unsafe {
+        ioctl(sock, libc::SIOCGIFMTU as libc::c_ulong, &mut ifr)?;
+        Ok(ifr.data.mtu)
+    }
+}
+
+/// Writes `name`'s MTU via `SIOCSIFMTU`. Portable; see `get_flags`.
+pub(crate) fn set_mtu(sock: c_int, name: &str, mtu: c_int) -> Result<()> {
+    let mut ifr = Ifreq::for_name(name)?;
+    ifr.data.mtu = mtu;
+
+    unsafe { ioctl(sock, libc::SIOCSIFMTU as libc::c_ulong, &mut ifr) }
+}
+
+/// Sets `name`'s hardware (MAC) address.
+///
+/// `SIOCSIFHWADDR` (and the `SIOCGIFHWADDR` read that precedes it, to preserve the address
+/// family byte) are Linux/Android-only `ioctl`s — `libc` doesn't even define the constants for
+/// macOS/BSD, where changing a link-layer address instead goes through `SIOCSIFLLADDR` and a
+/// `sockaddr_dl`. Until that path is implemented, report it as unsupported there rather than
+/// failing to compile.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_hardware_addr(sock: c_int, name: &str, addr: [u8; 6]) -> Result<()> {
+    let mut ifr = Ifreq::for_name(name)?;
+
+    unsafe {
+        ioctl(sock, libc::SIOCGIFHWADDR as libc::c_ulong, &mut ifr)?;
+        ifr.data.hwaddr.sa_data[..6].copy_from_slice(&(addr.map(|b| b as libc::c_char)));
+        ioctl(sock, libc::SIOCSIFHWADDR as libc::c_ulong, &mut ifr)
+    }
+}
+
+/// See the doc comment on the Linux/Android `set_hardware_addr` above.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn set_hardware_addr(_sock: c_int, _name: &str, _addr: [u8; 6]) -> Result<()> {
+    Err(InterfacesError::Unsupported(
+        "set_hardware_addr (SIOCSIFHWADDR is Linux/Android-only; macOS/BSD need SIOCSIFLLADDR)",
+    ))
+}
@@ -15,23 +15,47 @@ use std::fmt;
 use std::net;
 use libc::c_int;
 use std::ptr;
+use std::str::FromStr;
 
+// Android has no reliable static `getifaddrs` (see the `android` module), so `get_all()` there
+// walks the raw `getifaddrs` list itself instead of going through this crate.
+#[cfg(not(target_os = "android"))]
 extern crate network_interface;
 
+#[cfg(not(target_os = "android"))]
 use network_interface::NetworkInterface;
+#[cfg(not(target_os = "android"))]
 use network_interface::NetworkInterfaceConfig;
 
+#[cfg(not(target_os = "android"))]
 use network_interface::V4IfAddr;
+#[cfg(not(target_os = "android"))]
 use network_interface::V6IfAddr;
 
-use error::InterfacesError;
+use error::{InterfacesError, Result};
 use flags::InterfaceFlags;
 
-// mod error;
+mod ioctl;
+
+/// Android has no static `getifaddrs`/`freeifaddrs`; this resolves them via `dlopen` instead.
+#[cfg(target_os = "android")]
+mod android;
+
+/// Submodule containing this crate's error type.
+pub mod error;
 
 /// Submodule containing various flags.
 pub mod flags;
 
+/// Submodule containing default-gateway discovery.
+pub mod gateway;
+
+/// Submodule containing the optional `NETLINK_ROUTE`-based enumeration backend.
+///
+/// Requires the `netlink` feature, and only exists on Linux.
+#[cfg(all(target_os = "linux", feature = "netlink"))]
+pub mod netlink;
+
 /// `Kind` represents the interface family (equivalent to the `sa_family` field in the `sockaddr`
 /// structure).
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -84,6 +108,27 @@ impl fmt::Display for NextHop {
     }
 }
 
+/// Interface byte/packet counters.
+///
+/// Only populated by backends that can read them from the kernel (currently the
+/// `netlink`-feature-gated `Interface::get_all_netlink()`); other backends leave an interface's
+/// `statistics` as `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// Bytes received.
+    pub rx_bytes: u64,
+    /// Packets received.
+    pub rx_packets: u64,
+    /// Receive errors.
+    pub rx_errors: u64,
+    /// Bytes transmitted.
+    pub tx_bytes: u64,
+    /// Packets transmitted.
+    pub tx_packets: u64,
+    /// Transmit errors.
+    pub tx_errors: u64,
+}
+
 /// This structure represents a single address for a given interface.
 #[derive(Debug, Clone, Copy)]
 pub struct Address {
@@ -98,28 +143,314 @@ pub struct Address {
 
     /// The broadcast address or destination address, if it applies.
     pub hop: Option<NextHop>,
+
+    /// The hardware (MAC) address carried by this address, if `kind` is `Link` or `Packet`.
+    pub hardware_addr: Option<HardwareAddr>,
 }
 
+#[cfg(not(target_os = "android"))]
 fn to_address(addr: &network_interface::Addr) -> Address {
-    Address { 
+    Address {
         kind: {
             match addr {
                 network_interface::Addr::V4(V4IfAddr) => Kind::Ipv4,
                 network_interface::Addr::V6(V6IfAddr) => Kind::Ipv6
             }
-        }, 
-        addr: Some(net::SocketAddr::new(addr.ip(), 0)), 
+        },
+        addr: Some(net::SocketAddr::new(addr.ip(), 0)),
         mask: {
             match addr.netmask() {
                 Some(ip) => Some(net::SocketAddr::new(ip, 0)),
                 None => None
             }
-        }, 
+        },
         hop: {
             match addr.broadcast() {
                 Some(broadcast) => Some(NextHop::Broadcast(net::SocketAddr::new(broadcast, 0))),
                 None => None
             }
+        },
+        hardware_addr: None,
+    }
+}
+
+/// `network_interface`'s `getifaddrs` wrapper only surfaces `AF_INET`/`AF_INET6` addresses, so
+/// the link-layer address (`AF_LINK` on macOS/BSD, `AF_PACKET` on Linux) that `getifaddrs(3)`
+/// also returns for each interface never reaches `to_address`.  This walks the raw `getifaddrs`
+/// list itself to recover that one address per interface.
+///
+/// Android's `get_all()` doesn't need this: it walks the raw list itself and picks up the
+/// link-layer address inline (see `enumerate_interfaces`).
+#[cfg(not(target_os = "android"))]
+fn link_layer_address(name: &str) -> Option<Address> {
+    use std::ffi::CStr;
+
+    let mut ifap: *mut libc::ifaddrs = ptr::null_mut();
+    if call_getifaddrs(&mut ifap).is_err() {
+        return None;
+    }
+
+    let mut found = None;
+    let mut cur = ifap;
+
+    while !cur.is_null() {
+        let ifa = unsafe { &*cur };
+        cur = ifa.ifa_next;
+
+        if ifa.ifa_addr.is_null() {
+            continue;
+        }
+
+        let ifa_name = unsafe { CStr::from_ptr(ifa.ifa_name) };
+        if ifa_name.to_str() != Ok(name) {
+            continue;
+        }
+
+        if let Some(addr) = sys::parse_link_layer_sockaddr(ifa.ifa_addr) {
+            found = Some(addr);
+            break;
+        }
+    }
+
+    call_freeifaddrs(ifap);
+
+    found
+}
+
+/// Resolves `getifaddrs`, which on every platform but Android is simply `libc::getifaddrs`; see
+/// the `android` module for why Android needs its own path.
+#[cfg(not(target_os = "android"))]
+fn call_getifaddrs(ifap: *mut *mut libc::ifaddrs) -> Result<()> {
+    if unsafe { libc::getifaddrs(ifap) } != 0 {
+        return Err(InterfacesError::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// See `call_getifaddrs` above.
+#[cfg(target_os = "android")]
+fn call_getifaddrs(ifap: *mut *mut libc::ifaddrs) -> Result<()> {
+    android::getifaddrs(ifap)
+}
+
+#[cfg(not(target_os = "android"))]
+fn call_freeifaddrs(ifap: *mut libc::ifaddrs) {
+    unsafe {
+        libc::freeifaddrs(ifap);
+    }
+}
+
+#[cfg(target_os = "android")]
+fn call_freeifaddrs(ifap: *mut libc::ifaddrs) {
+    android::freeifaddrs(ifap)
+}
+
+/// Builds `(name, addresses, flags)` tuples by walking the raw `getifaddrs(3)` list ourselves,
+/// via the `dlopen`'d symbols in the `android` module (through `call_getifaddrs`/
+/// `call_freeifaddrs`), instead of `network_interface::NetworkInterface::show()`'s statically
+/// linked `getifaddrs`.
+#[cfg(target_os = "android")]
+fn enumerate_interfaces() -> Result<Vec<(String, Vec<Address>, InterfaceFlags)>> {
+    use std::ffi::CStr;
+
+    let mut ifap: *mut libc::ifaddrs = ptr::null_mut();
+    call_getifaddrs(&mut ifap)?;
+
+    let mut interfaces: Vec<(String, Vec<Address>, InterfaceFlags)> = Vec::new();
+    let mut cur = ifap;
+
+    while !cur.is_null() {
+        let ifa = unsafe { &*cur };
+        cur = ifa.ifa_next;
+
+        let name = unsafe { CStr::from_ptr(ifa.ifa_name) }.to_string_lossy().into_owned();
+        let flags = InterfaceFlags::from_bits_truncate(ifa.ifa_flags as u32);
+
+        let index = match interfaces.iter().position(|(n, _, _)| *n == name) {
+            Some(index) => index,
+            None => {
+                interfaces.push((name, Vec::new(), flags));
+                interfaces.len() - 1
+            }
+        };
+
+        if ifa.ifa_addr.is_null() {
+            continue;
+        }
+
+        if let Some(addr) = sys::parse_link_layer_sockaddr(ifa.ifa_addr) {
+            interfaces[index].1.push(addr);
+        } else if let Some(addr) = sockaddr_to_address(ifa.ifa_addr, ifa.ifa_netmask) {
+            interfaces[index].1.push(addr);
+        }
+    }
+
+    call_freeifaddrs(ifap);
+
+    Ok(interfaces)
+}
+
+/// Converts a raw `AF_INET`/`AF_INET6` `ifa_addr`/`ifa_netmask` pair into an `Address`.
+///
+/// Doesn't attempt to recover the broadcast/destination address: unlike `ifa_addr`/
+/// `ifa_netmask`, the `ifa_ifu` union's layout isn't stable enough across `libc`'s Android
+/// bindings to pick apart by hand here.
+#[cfg(target_os = "android")]
+fn sockaddr_to_address(addr: *const libc::sockaddr, netmask: *const libc::sockaddr) -> Option<Address> {
+    fn kind_and_ip(sa: *const libc::sockaddr) -> Option<(Kind, net::IpAddr)> {
+        if sa.is_null() {
+            return None;
+        }
+
+        match unsafe { (*sa).sa_family as libc::c_int } {
+            libc::AF_INET => {
+                let sin = unsafe { &*(sa as *const libc::sockaddr_in) };
+                let ip = net::Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr));
+                Some((Kind::Ipv4, net::IpAddr::V4(ip)))
+            }
+            libc::AF_INET6 => {
+                let sin6 = unsafe { &*(sa as *const libc::sockaddr_in6) };
+                Some((Kind::Ipv6, net::IpAddr::V6(net::Ipv6Addr::from(sin6.sin6_addr.s6_addr))))
+            }
+            _ => None,
+        }
+    }
+
+    let (kind, ip) = kind_and_ip(addr)?;
+    let mask = kind_and_ip(netmask).map(|(_, ip)| net::SocketAddr::new(ip, 0));
+
+    Some(Address {
+        kind,
+        addr: Some(net::SocketAddr::new(ip, 0)),
+        mask,
+        hop: None,
+        hardware_addr: None,
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use {Address, HardwareAddr, Kind};
+
+    /// Interprets an `AF_PACKET` `sockaddr` (actually a `sockaddr_ll`) into a `Kind::Packet`
+    /// address.
+    pub(super) fn parse_link_layer_sockaddr(sa: *const libc::sockaddr) -> Option<Address> {
+        let sa = unsafe { &*sa };
+        if sa.sa_family as libc::c_int != libc::AF_PACKET {
+            return None;
+        }
+
+        let sll = unsafe { &*(sa as *const libc::sockaddr as *const libc::sockaddr_ll) };
+        let len = sll.sll_halen as usize;
+        let hardware_addr = HardwareAddr::from_bytes(&sll.sll_addr[..len.min(sll.sll_addr.len())]).ok();
+
+        Some(Address {
+            kind: Kind::Packet,
+            addr: None,
+            mask: None,
+            hop: None,
+            hardware_addr,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod sys {
+    use {Address, HardwareAddr, Kind};
+
+    use dl::hwaddr_from_sockaddr_dl;
+
+    /// Interprets an `AF_LINK` `sockaddr` (actually a `sockaddr_dl`) into a `Kind::Link`
+    /// address, per the `sdl_data[sdl_nlen..sdl_nlen + sdl_alen]` convention described in
+    /// `<net/if_dl.h>`.
+    pub(super) fn parse_link_layer_sockaddr(sa: *const libc::sockaddr) -> Option<Address> {
+        let sa = unsafe { &*sa };
+        if sa.sa_family as libc::c_int != libc::AF_LINK {
+            return None;
+        }
+
+        let sdl = unsafe { &*(sa as *const libc::sockaddr as *const libc::sockaddr_dl) };
+        let hardware_addr = hwaddr_from_sockaddr_dl(sdl);
+
+        Some(Address {
+            kind: Kind::Link,
+            addr: None,
+            mask: None,
+            hop: None,
+            hardware_addr,
+        })
+    }
+}
+
+/// `sockaddr_dl` (`AF_LINK`) parsing shared by this crate's BSD/macOS address parsing (above) and
+/// `gateway`'s BSD/macOS routing-socket backend, which both need to pull a hardware address out of
+/// the same kernel structure.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod dl {
+    use std::slice;
+
+    use HardwareAddr;
+
+    /// Extracts the `sdl_data[sdl_nlen..sdl_nlen + sdl_alen]` hardware-address bytes from a
+    /// `sockaddr_dl`.
+    ///
+    /// `libc`'s `sockaddr_dl` declares `sdl_data` as a fixed `[c_char; 12]` array, but that's
+    /// just bindgen's guess at a common size: the kernel actually allocates (and `sdl_len`
+    /// describes) a buffer long enough for the interface name *and* its link address, which for
+    /// names like `bridge100` overruns 12 bytes. Re-derive the real byte range from `sdl_len`
+    /// instead of trusting the fixed-size field, so long interface names don't silently lose
+    /// their MAC.
+    pub(crate) fn hwaddr_from_sockaddr_dl(sdl: &libc::sockaddr_dl) -> Option<HardwareAddr> {
+        let nlen = sdl.sdl_nlen as usize;
+        let alen = sdl.sdl_alen as usize;
+
+        let base = sdl as *const libc::sockaddr_dl as *const u8;
+        let data_offset = (sdl.sdl_data.as_ptr() as *const u8 as usize) - (base as usize);
+        let total_len = (sdl.sdl_len as usize).max(data_offset + nlen + alen.min(sdl.sdl_data.len()));
+
+        // Safe: `sdl_len` (when the kernel filled in this `sockaddr_dl`) describes the real size
+        // of the allocation backing `sdl`, which is always at least as large as the fixed struct
+        // itself, so reading `total_len` bytes from `sdl`'s address never runs past the buffer
+        // the caller gave us.
+        let whole = unsafe { slice::from_raw_parts(base, total_len) };
+
+        whole
+            .get(data_offset + nlen..data_offset + nlen + alen)
+            .and_then(|bytes| HardwareAddr::from_bytes(bytes).ok())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::mem;
+
+        /// Hand-builds a `sockaddr_dl`-shaped buffer whose `sdl_data` holds a name longer than
+        /// fits in the fixed 12-byte field plus a trailing 6-byte MAC, mirroring what the kernel
+        /// hands back for an interface like `bridge100`.
+        #[test]
+        fn test_hwaddr_from_sockaddr_dl_handles_long_names() {
+            let name = b"bridge100";
+            let mac = [0x02u8, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+            let header_len = mem::size_of::<libc::sockaddr_dl>()
+                - mem::size_of_val(&unsafe { mem::zeroed::<libc::sockaddr_dl>() }.sdl_data);
+            let total_len = header_len + name.len() + mac.len();
+
+            let mut buf = vec![0u8; total_len];
+            {
+                let sdl = unsafe { &mut *(buf.as_mut_ptr() as *mut libc::sockaddr_dl) };
+                sdl.sdl_len = total_len as u8;
+                sdl.sdl_family = libc::AF_LINK as u8;
+                sdl.sdl_nlen = name.len() as u8;
+                sdl.sdl_alen = mac.len() as u8;
+            }
+            buf[header_len..header_len + name.len()].copy_from_slice(name);
+            buf[header_len + name.len()..total_len].copy_from_slice(&mac);
+
+            let sdl = unsafe { &*(buf.as_ptr() as *const libc::sockaddr_dl) };
+            let hardware_addr = hwaddr_from_sockaddr_dl(sdl).expect("hardware address");
+            assert_eq!(hardware_addr.as_bytes(), &mac);
         }
     }
 }
@@ -181,6 +512,27 @@ impl HardwareAddr {
         let &HardwareAddr(ref arr) = self;
         arr
     }
+
+    /// Builds a `HardwareAddr` from a 6-byte slice, as returned by e.g. `SIOCGIFHWADDR` or a
+    /// `sockaddr_dl`/`sockaddr_ll`'s link-layer address bytes.
+    ///
+    /// ```
+    /// # use interfaces::HardwareAddr;
+    /// let addr = HardwareAddr::from_bytes(&[0, 1, 2, 3, 4, 5]).unwrap();
+    /// assert_eq!(addr.as_string(), "00:01:02:03:04:05");
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<HardwareAddr> {
+        if bytes.len() != 6 {
+            return Err(InterfacesError::ParseError(format!(
+                "expected 6 bytes for a hardware address, got {}",
+                bytes.len()
+            )));
+        }
+
+        let mut arr = [0u8; 6];
+        arr.copy_from_slice(bytes);
+        Ok(HardwareAddr(arr))
+    }
 }
 
 impl fmt::Display for HardwareAddr {
@@ -189,6 +541,38 @@ impl fmt::Display for HardwareAddr {
     }
 }
 
+impl FromStr for HardwareAddr {
+    type Err = InterfacesError;
+
+    /// Parses a hardware address out of the standard colon-separated format, e.g.
+    /// `"aa:bb:cc:dd:ee:ff"`.
+    ///
+    /// ```
+    /// # use interfaces::HardwareAddr;
+    /// # use std::str::FromStr;
+    /// let addr = HardwareAddr::from_str("aa:bb:cc:dd:ee:ff").unwrap();
+    /// assert_eq!(addr.as_string(), "aa:bb:cc:dd:ee:ff");
+    /// ```
+    fn from_str(s: &str) -> Result<HardwareAddr> {
+        let parts: Vec<&str> = s.split(':').collect();
+
+        if parts.len() != 6 {
+            return Err(InterfacesError::ParseError(format!(
+                "expected 6 colon-separated octets, got {}",
+                parts.len()
+            )));
+        }
+
+        let mut arr = [0u8; 6];
+        for (dst, part) in arr.iter_mut().zip(parts.iter()) {
+            *dst = u8::from_str_radix(part, 16)
+                .map_err(|_| InterfacesError::ParseError(format!("invalid octet: {}", part)))?;
+        }
+
+        Ok(HardwareAddr(arr))
+    }
+}
+
 /// The `Interface` structure represents a single interface on the system.  It also contains
 /// methods to control the interface.
 #[derive(Debug)]
@@ -206,12 +590,76 @@ pub struct Interface {
     /// the first address).
     pub flags: InterfaceFlags,
 
-    // Information socket
+    /// The hardware (MAC) address of this interface, if it has one.  On macOS/BSD loopback
+    /// interfaces have none; on Linux the kernel reports `00:00:00:00:00:00` for loopback, and
+    /// that is what this holds.
+    pub hardware_addr: Option<HardwareAddr>,
+
+    /// This interface's kernel interface index.
+    ///
+    /// Only populated by backends that can read it (currently `get_all_netlink()`); `get_all()`
+    /// leaves this at `0`.
+    pub index: u32,
+
+    /// This interface's traffic counters, if the backend that enumerated it could read them.
+    pub statistics: Option<Statistics>,
+
+    /// This interface's MTU, if the backend that enumerated it could read it.
+    ///
+    /// Only populated by `get_all_netlink()`; `get_all()` leaves this at `None` since querying it
+    /// would mean an extra `SIOCGIFMTU` round-trip per interface on top of the ones it already
+    /// does for the hardware address and flags — call `Interface::get_mtu()` instead if you need
+    /// it from that backend.
+    pub mtu: Option<u32>,
+
+    // Socket used to issue the `ioctl`s backing this interface's control methods.
     sock: c_int,
 }
 
+/// Looks up `name`'s hardware address via `SIOCGIFHWADDR`.
+///
+/// This works on Linux, where `SIOCGIFHWADDR` is implemented for (almost) every interface type.
+/// On macOS/BSD the link-layer address instead comes from the interface's `AF_LINK` address (see
+/// `link_layer_address`), so this always returns `Ok(None)` there.
+#[cfg(target_os = "linux")]
+fn hardware_addr_for(name: &str, sock: c_int) -> Result<Option<HardwareAddr>> {
+    let mut ifr = ioctl::Ifreq::for_name(name)?;
+
+    unsafe {
+        ioctl::ioctl(sock, libc::SIOCGIFHWADDR as libc::c_ulong, &mut ifr)?;
+
+        let mut bytes = [0u8; 6];
+        for (dst, src) in bytes.iter_mut().zip(ifr.data.hwaddr.sa_data[..6].iter()) {
+            *dst = *src as u8;
+        }
+
+        Ok(Some(HardwareAddr::from_bytes(&bytes)?))
+    }
+}
+
+/// On macOS/BSD the hardware address is populated from each interface's `AF_LINK` address (see
+/// `link_layer_address`) rather than via an `ioctl`.
+#[cfg(not(target_os = "linux"))]
+fn hardware_addr_for(_name: &str, _sock: c_int) -> Result<Option<HardwareAddr>> {
+    Ok(None)
+}
+
+/// Opens the `AF_INET`/`SOCK_DGRAM` socket used to issue interface-control `ioctl`s.  The socket
+/// is never connected or bound; its only purpose is to give the kernel something to dispatch
+/// `SIOC*IF*` requests against.
+fn open_ioctl_socket() -> Result<c_int> {
+    let sock = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+
+    if sock < 0 {
+        return Err(InterfacesError::last_os_error());
+    }
+
+    Ok(sock)
+}
+
 impl Interface {
     /// Retrieve a list of all interfaces on this system.
+    #[cfg(not(target_os = "android"))]
     pub fn get_all() -> Result<Vec<Interface>> {
 
         let network_interfaces: Vec<NetworkInterface> = NetworkInterface::show().unwrap();
@@ -219,15 +667,38 @@ impl Interface {
 
         for netif in network_interfaces.iter() {
             let mut addrs: Vec<Address> = Vec::new();
-            
+
             for addr in &netif.addr {
                 addrs.push(to_address(&addr));
             }
+            if let Some(link_addr) = link_layer_address(&netif.name) {
+                addrs.push(link_addr);
+            }
+
+            let sock = open_ioctl_socket()?;
+
+            // `SIOCGIFHWADDR` can legitimately fail for a single interface (e.g. `ENODEV` if it
+            // disappeared between the `getifaddrs()` snapshot and here, or an interface type
+            // that doesn't implement the ioctl); don't let that take down enumeration for every
+            // other interface.
+            let hardware_addr = hardware_addr_for(&netif.name, sock).ok().flatten()
+                .or_else(|| addrs.iter().find_map(|a| a.hardware_addr));
+
+            // `SIOCGIFFLAGS` can fail for the same reasons as the hwaddr lookup above; fall back
+            // to an empty flag set rather than losing the whole interface over it.
+            let flags = ioctl::get_flags(sock, &netif.name)
+                .map(|bits| InterfaceFlags::from_bits_truncate(bits as u32))
+                .unwrap_or_else(|_| InterfaceFlags::empty());
+
             let intf = Interface {
                 name: netif.name.clone(),
                 addresses: addrs,
-                flags: InterfaceFlags::IFF_UP,
-                sock: 1,
+                flags,
+                hardware_addr,
+                index: 0,
+                statistics: None,
+                mtu: None,
+                sock,
             };
             res.push(intf);
         }
@@ -235,6 +706,84 @@ impl Interface {
         Ok(res)
     }
 
+    /// Retrieve a list of all interfaces on this system.
+    ///
+    /// Unlike every other platform, this doesn't go through
+    /// `network_interface::NetworkInterface::show()` — that's backed by a statically-linked
+    /// `getifaddrs`, which is exactly what's unreliable across Android API levels. Instead this
+    /// walks the raw `getifaddrs` list produced by the `dlopen`'d symbols in the `android` module.
+    #[cfg(target_os = "android")]
+    pub fn get_all() -> Result<Vec<Interface>> {
+        let mut res: Vec<Interface> = Vec::new();
+
+        for (name, addrs, flags) in enumerate_interfaces()? {
+            let sock = open_ioctl_socket()?;
+            let hardware_addr = hardware_addr_for(&name, sock)?
+                .or_else(|| addrs.iter().find_map(|a| a.hardware_addr));
+
+            res.push(Interface {
+                name,
+                addresses: addrs,
+                flags,
+                hardware_addr,
+                index: 0,
+                statistics: None,
+                mtu: None,
+                sock,
+            });
+        }
+
+        Ok(res)
+    }
+
+    /// Returns whether or not this interface is up, per its current kernel flags.
+    pub fn is_up(&self) -> Result<bool> {
+        Ok(self.get_flags()?.contains(InterfaceFlags::IFF_UP))
+    }
+
+    /// Brings this interface up (`true`) or takes it down (`false`).
+    pub fn set_up(&mut self, up: bool) -> Result<()> {
+        let mut flags = self.get_flags()?;
+        flags.set(InterfaceFlags::IFF_UP, up);
+        self.set_flags(flags)
+    }
+
+    /// Fetches this interface's current flags from the kernel via `SIOCGIFFLAGS`.
+    pub fn get_flags(&self) -> Result<InterfaceFlags> {
+        let flags = ioctl::get_flags(self.sock, &self.name)?;
+        Ok(InterfaceFlags::from_bits_truncate(flags as u32))
+    }
+
+    /// Writes this interface's flags back to the kernel via `SIOCSIFFLAGS`.
+    pub fn set_flags(&mut self, flags: InterfaceFlags) -> Result<()> {
+        ioctl::set_flags(self.sock, &self.name, flags.bits() as libc::c_short)
+    }
+
+    /// Fetches this interface's MTU via `SIOCGIFMTU`.
+    pub fn get_mtu(&self) -> Result<u32> {
+        Ok(ioctl::get_mtu(self.sock, &self.name)? as u32)
+    }
+
+    /// Sets this interface's MTU via `SIOCSIFMTU`.
+    pub fn set_mtu(&mut self, mtu: u32) -> Result<()> {
+        ioctl::set_mtu(self.sock, &self.name, mtu as libc::c_int)
+    }
+
+    /// Sets this interface's hardware (MAC) address.
+    ///
+    /// Linux/Android only; on macOS/BSD this returns `InterfacesError::Unsupported` (see
+    /// `ioctl::set_hardware_addr`).
+    pub fn set_hardware_addr(&mut self, addr: HardwareAddr) -> Result<()> {
+        ioctl::set_hardware_addr(self.sock, &self.name, addr.0)
+    }
+}
+
+impl Drop for Interface {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.sock);
+        }
+    }
 }
 
 impl PartialEq for Interface {
@@ -0,0 +1,446 @@
+//! An optional `NETLINK_ROUTE`-based enumeration backend for Linux.
+//!
+//! `Interface::get_all()` is built on top of `network_interface`'s `getifaddrs` wrapper, which
+//! has no notion of an interface's kernel index or its traffic counters.  `get_all_netlink()`
+//! drives `RTM_GETLINK`/`RTM_GETADDR` dumps over a raw `AF_NETLINK`/`NETLINK_ROUTE` socket
+//! instead, the same way the `netlink-packet-route` ecosystem does, to recover that information.
+//!
+//! This backend is gated behind the `netlink` feature since it only exists on Linux and pulls in
+//! a fair amount of low-level parsing that most callers don't need.
+
+use std::collections::HashMap;
+use std::mem;
+use std::net::IpAddr;
+
+use libc::{self, c_int};
+
+use error::{InterfacesError, Result};
+use flags::InterfaceFlags;
+use {Address, HardwareAddr, Interface, Statistics};
+
+// Not every constant this module needs is exposed by the `libc` crate; the ones below come
+// straight from `linux/if_link.h` and `linux/if_addr.h`.
+const IFLA_ADDRESS: u16 = 1;
+const IFLA_IFNAME: u16 = 3;
+const IFLA_MTU: u16 = 4;
+const IFLA_STATS64: u16 = 23;
+
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+
+const NLMSG_ALIGNTO: usize = 4;
+const RTATTR_ALIGNTO: usize = 4;
+
+impl Interface {
+    /// Retrieves a list of all interfaces on this system via `NETLINK_ROUTE`, rather than
+    /// `network_interface`'s `getifaddrs` wrapper.  Unlike `get_all()`, the returned interfaces
+    /// have `index` and `statistics` populated.
+    pub fn get_all_netlink() -> Result<Vec<Interface>> {
+        let sock = NetlinkSocket::open()?;
+        let links = dump(sock.0, libc::RTM_GETLINK, libc::AF_UNSPEC as u8, parse_link)?;
+        let addrs = dump(sock.0, libc::RTM_GETADDR, libc::AF_UNSPEC as u8, parse_addr)?;
+        drop(sock);
+
+        let mut addrs_by_index: HashMap<i32, Vec<Address>> = HashMap::new();
+        for (index, addr) in addrs {
+            addrs_by_index.entry(index).or_default().push(addr);
+        }
+
+        let mut res = Vec::with_capacity(links.len());
+        for link in links {
+            let ioctl_sock = super::open_ioctl_socket()?;
+            res.push(Interface {
+                name: link.name.clone(),
+                addresses: addrs_by_index.remove(&link.index).unwrap_or_default(),
+                flags: link.flags,
+                hardware_addr: link.hardware_addr,
+                index: link.index as u32,
+                statistics: link.statistics,
+                mtu: link.mtu,
+                sock: ioctl_sock,
+            });
+        }
+
+        Ok(res)
+    }
+}
+
+struct Link {
+    index: i32,
+    name: String,
+    flags: InterfaceFlags,
+    hardware_addr: Option<HardwareAddr>,
+    statistics: Option<Statistics>,
+    mtu: Option<u32>,
+}
+
+/// Owns the `AF_NETLINK` socket used by `get_all_netlink()`'s two `dump()` round-trips, closing
+/// it on drop so an
+/// early `?` return from either `dump()` call (a `recv` failure or an `NLMSG_ERROR` reply) can't
+/// leak the fd.
+struct NetlinkSocket(c_int);
+
+impl NetlinkSocket {
+    fn open() -> Result<NetlinkSocket> {
+        let sock = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_RAW, libc::NETLINK_ROUTE) };
+
+        if sock < 0 {
+            return Err(InterfacesError::last_os_error());
+        }
+
+        Ok(NetlinkSocket(sock))
+    }
+}
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+/// Sends a `NLM_F_REQUEST | NLM_F_DUMP` request of type `rtm_type`, then reads and parses every
+/// reply message with `parse_one` until `NLMSG_DONE`.
+fn dump<T, F>(sock: c_int, rtm_type: u16, family: u8, parse_one: F) -> Result<Vec<T>>
+where
+    F: Fn(&[u8]) -> Option<T>,
+{
+    send_dump_request(sock, rtm_type, family)?;
+
+    let mut out = Vec::new();
+    let mut buf = [0u8; 8192];
+
+    'recv: loop {
+        let n = unsafe {
+            libc::recv(sock, buf.as_mut_ptr() as *mut _, buf.len(), 0)
+        };
+
+        if n < 0 {
+            return Err(InterfacesError::last_os_error());
+        }
+
+        let mut offset = 0;
+        let received = &buf[..n as usize];
+
+        while offset + mem::size_of::<libc::nlmsghdr>() <= received.len() {
+            let nlh = unsafe { &*(received[offset..].as_ptr() as *const libc::nlmsghdr) };
+            let msg_len = nlh.nlmsg_len as usize;
+            if msg_len < mem::size_of::<libc::nlmsghdr>() || offset + msg_len > received.len() {
+                break;
+            }
+
+            match nlh.nlmsg_type as i32 {
+                libc::NLMSG_DONE => break 'recv,
+                libc::NLMSG_ERROR => return Err(InterfacesError::ParseError(
+                    "netlink returned an error reply".to_owned(),
+                )),
+                _ => {
+                    let payload = &received[offset + mem::size_of::<libc::nlmsghdr>()..offset + msg_len];
+                    if let Some(item) = parse_one(payload) {
+                        out.push(item);
+                    }
+                }
+            }
+
+            offset += align(msg_len, NLMSG_ALIGNTO);
+        }
+    }
+
+    Ok(out)
+}
+
+fn send_dump_request(sock: c_int, rtm_type: u16, family: u8) -> Result<()> {
+    #[repr(C)]
+    struct Request {
+        nlh: libc::nlmsghdr,
+        ifi: libc::ifinfomsg,
+    }
+
+    let mut req: Request = unsafe { mem::zeroed() };
+    req.nlh.nlmsg_len = mem::size_of::<Request>() as u32;
+    req.nlh.nlmsg_type = rtm_type;
+    req.nlh.nlmsg_flags = (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16;
+    req.ifi.ifi_family = family as libc::c_uchar;
+
+    let sent = unsafe {
+        libc::send(
+            sock,
+            &req as *const Request as *const _,
+            mem::size_of::<Request>(),
+            0,
+        )
+    };
+
+    if sent < 0 {
+        return Err(InterfacesError::last_os_error());
+    }
+
+    Ok(())
+}
+
+fn align(len: usize, to: usize) -> usize {
+    (len + to - 1) & !(to - 1)
+}
+
+/// Walks the `rtattr`s following a fixed-size header (an `ifinfomsg` or `ifaddrmsg`), returning
+/// each attribute's type and payload.
+fn attrs(body: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut out = Vec::new();
+    let mut offset = 0;
+
+    while offset + mem::size_of::<libc::rtattr>() <= body.len() {
+        let rta = unsafe { &*(body[offset..].as_ptr() as *const libc::rtattr) };
+        let rta_len = rta.rta_len as usize;
+        if rta_len < mem::size_of::<libc::rtattr>() || offset + rta_len > body.len() {
+            break;
+        }
+
+        let payload = &body[offset + mem::size_of::<libc::rtattr>()..offset + rta_len];
+        out.push((rta.rta_type, payload));
+        offset += align(rta_len, RTATTR_ALIGNTO);
+    }
+
+    out
+}
+
+fn parse_link(payload: &[u8]) -> Option<Link> {
+    if payload.len() < mem::size_of::<libc::ifinfomsg>() {
+        return None;
+    }
+
+    let ifi = unsafe { &*(payload.as_ptr() as *const libc::ifinfomsg) };
+    let body = &payload[mem::size_of::<libc::ifinfomsg>()..];
+
+    let mut name = None;
+    let mut hardware_addr = None;
+    let mut statistics = None;
+    let mut mtu = None;
+
+    for (ty, data) in attrs(body) {
+        match ty {
+            IFLA_IFNAME => {
+                name = data
+                    .iter()
+                    .position(|&b| b == 0)
+                    .map(|nul| String::from_utf8_lossy(&data[..nul]).into_owned());
+            }
+            IFLA_ADDRESS => {
+                hardware_addr = HardwareAddr::from_bytes(data).ok();
+            }
+            IFLA_MTU if data.len() >= 4 => {
+                let mut bytes = [0u8; 4];
+                bytes.copy_from_slice(&data[..4]);
+                mtu = Some(u32::from_ne_bytes(bytes));
+            }
+            IFLA_STATS64 if data.len() >= 8 * 10 => {
+                let u64_at = |n: usize| {
+                    let mut bytes = [0u8; 8];
+                    bytes.copy_from_slice(&data[n * 8..n * 8 + 8]);
+                    u64::from_ne_bytes(bytes)
+                };
+
+                // Layout of `struct rtnl_link_stats64`: rx_packets, tx_packets, rx_bytes,
+                // tx_bytes, rx_errors, tx_errors, ...
+                statistics = Some(Statistics {
+                    rx_packets: u64_at(0),
+                    tx_packets: u64_at(1),
+                    rx_bytes: u64_at(2),
+                    tx_bytes: u64_at(3),
+                    rx_errors: u64_at(4),
+                    tx_errors: u64_at(5),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Some(Link {
+        index: ifi.ifi_index,
+        name: name?,
+        flags: InterfaceFlags::from_bits_truncate(ifi.ifi_flags),
+        hardware_addr,
+        statistics,
+        mtu,
+    })
+}
+
+fn parse_addr(payload: &[u8]) -> Option<(i32, Address)> {
+    if payload.len() < mem::size_of::<libc::ifaddrmsg>() {
+        return None;
+    }
+
+    let ifa = unsafe { &*(payload.as_ptr() as *const libc::ifaddrmsg) };
+    let body = &payload[mem::size_of::<libc::ifaddrmsg>()..];
+
+    let family = ifa.ifa_family as c_int;
+    let mut ip_addr = None;
+
+    for (ty, data) in attrs(body) {
+        if ty != IFA_ADDRESS && ty != IFA_LOCAL {
+            continue;
+        }
+
+        ip_addr = match (family, data.len()) {
+            (libc::AF_INET, 4) => {
+                let mut b = [0u8; 4];
+                b.copy_from_slice(data);
+                Some(IpAddr::from(b))
+            }
+            (libc::AF_INET6, 16) => {
+                let mut b = [0u8; 16];
+                b.copy_from_slice(data);
+                Some(IpAddr::from(b))
+            }
+            _ => None,
+        };
+
+        if ip_addr.is_some() {
+            break;
+        }
+    }
+
+    let kind = match family {
+        libc::AF_INET => ::Kind::Ipv4,
+        libc::AF_INET6 => ::Kind::Ipv6,
+        other => ::Kind::Unknown(other),
+    };
+
+    Some((
+        ifa.ifa_index as i32,
+        Address {
+            kind,
+            addr: ip_addr.map(|ip| ::std::net::SocketAddr::new(ip, 0)),
+            mask: None,
+            hop: None,
+            hardware_addr: None,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Appends one `rtattr`-framed attribute (header + payload, padded to `RTATTR_ALIGNTO`) to
+    /// `buf`, mirroring what `attrs()` expects to walk.
+    fn push_rtattr(buf: &mut Vec<u8>, ty: u16, payload: &[u8]) {
+        let rta_len = (mem::size_of::<libc::rtattr>() + payload.len()) as u16;
+        buf.extend_from_slice(&rta_len.to_ne_bytes());
+        buf.extend_from_slice(&ty.to_ne_bytes());
+        buf.extend_from_slice(payload);
+        buf.resize(align(buf.len(), RTATTR_ALIGNTO), 0);
+    }
+
+    #[test]
+    fn test_attrs_walks_multiple_padded_attributes() {
+        let mut body = Vec::new();
+        push_rtattr(&mut body, 7, &[0xAA, 0xBB]);
+        push_rtattr(&mut body, 9, &[0x01, 0x02, 0x03, 0x04, 0x05]);
+
+        let parsed = attrs(&body);
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0], (7, &[0xAAu8, 0xBB][..]));
+        assert_eq!(parsed[1], (9, &[0x01u8, 0x02, 0x03, 0x04, 0x05][..]));
+    }
+
+    #[test]
+    fn test_attrs_stops_on_truncated_trailing_attribute() {
+        let mut body = Vec::new();
+        push_rtattr(&mut body, 7, &[0xAA, 0xBB]);
+        body.extend_from_slice(&[0xFF, 0xFF]); // a fragment too short to hold even an `rtattr` header
+
+        let parsed = attrs(&body);
+
+        assert_eq!(parsed, vec![(7, &[0xAAu8, 0xBB][..])]);
+    }
+
+    fn push_ifinfomsg(buf: &mut Vec<u8>, index: i32, flags: u32) {
+        let mut ifi: libc::ifinfomsg = unsafe { mem::zeroed() };
+        ifi.ifi_index = index;
+        ifi.ifi_flags = flags;
+
+        let bytes = unsafe {
+            ::std::slice::from_raw_parts(&ifi as *const _ as *const u8, mem::size_of::<libc::ifinfomsg>())
+        };
+        buf.extend_from_slice(bytes);
+    }
+
+    #[test]
+    fn test_parse_link_reads_name_hwaddr_mtu_and_stats() {
+        let mac = [0x02u8, 0x11, 0x22, 0x33, 0x44, 0x55];
+        let mut stats = [0u8; 80];
+        stats[0..8].copy_from_slice(&42u64.to_ne_bytes()); // rx_packets
+        stats[16..24].copy_from_slice(&1234u64.to_ne_bytes()); // rx_bytes
+
+        let mut payload = Vec::new();
+        push_ifinfomsg(&mut payload, 3, libc::IFF_UP as u32);
+        push_rtattr(&mut payload, IFLA_IFNAME, b"eth0\0");
+        push_rtattr(&mut payload, IFLA_ADDRESS, &mac);
+        push_rtattr(&mut payload, IFLA_MTU, &1500u32.to_ne_bytes());
+        push_rtattr(&mut payload, IFLA_STATS64, &stats);
+
+        let link = parse_link(&payload).expect("a parsed link");
+
+        assert_eq!(link.index, 3);
+        assert_eq!(link.name, "eth0");
+        assert_eq!(link.hardware_addr, HardwareAddr::from_bytes(&mac).ok());
+        assert_eq!(link.mtu, Some(1500));
+        assert!(link.flags.contains(InterfaceFlags::IFF_UP));
+
+        let statistics = link.statistics.expect("statistics");
+        assert_eq!(statistics.rx_packets, 42);
+        assert_eq!(statistics.rx_bytes, 1234);
+    }
+
+    #[test]
+    fn test_parse_link_returns_none_without_an_ifname_attribute() {
+        let mut payload = Vec::new();
+        push_ifinfomsg(&mut payload, 3, 0);
+
+        assert!(parse_link(&payload).is_none());
+    }
+
+    fn push_ifaddrmsg(buf: &mut Vec<u8>, family: u8, index: u32) {
+        let ifa = libc::ifaddrmsg {
+            ifa_family: family,
+            ifa_prefixlen: 0,
+            ifa_flags: 0,
+            ifa_scope: 0,
+            ifa_index: index,
+        };
+        let bytes = unsafe {
+            ::std::slice::from_raw_parts(&ifa as *const _ as *const u8, mem::size_of::<libc::ifaddrmsg>())
+        };
+        buf.extend_from_slice(bytes);
+    }
+
+    #[test]
+    fn test_parse_addr_reads_ipv4_local_address() {
+        let mut payload = Vec::new();
+        push_ifaddrmsg(&mut payload, libc::AF_INET as u8, 4);
+        push_rtattr(&mut payload, IFA_LOCAL, &[192, 168, 1, 42]);
+
+        let (index, address) = parse_addr(&payload).expect("a parsed address");
+
+        assert_eq!(index, 4);
+        assert_eq!(address.kind, ::Kind::Ipv4);
+        assert_eq!(address.addr.map(|sa| sa.ip()), Some(IpAddr::from([192, 168, 1, 42])));
+    }
+
+    #[test]
+    fn test_parse_addr_reads_ipv6_address() {
+        let octets = [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+        let mut payload = Vec::new();
+        push_ifaddrmsg(&mut payload, libc::AF_INET6 as u8, 5);
+        push_rtattr(&mut payload, IFA_ADDRESS, &octets);
+
+        let (index, address) = parse_addr(&payload).expect("a parsed address");
+
+        assert_eq!(index, 5);
+        assert_eq!(address.kind, ::Kind::Ipv6);
+        assert_eq!(address.addr.map(|sa| sa.ip()), Some(IpAddr::from(octets)));
+    }
+}